@@ -34,6 +34,7 @@ impl App {
             email_addr,
             config.email_config.auth_token.clone(),
             config.email_config.timeout(),
+            config.email_config.retry_config(),
         )?;
         let tm = TemplateManager::init();
 
@@ -0,0 +1,13 @@
+use tracing::info;
+
+use crate::{web, App, Result};
+
+/// Run the application, consuming the listener bound in [`App::build_from_config`].
+pub async fn serve(app: App) -> Result<()> {
+    let router = web::routes(app.app_state);
+
+    info!("Serving requests...");
+    axum::serve(app.listener, router).await?;
+
+    Ok(())
+}
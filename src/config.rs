@@ -0,0 +1,164 @@
+//! Typed application configuration, assembled from `configuration/base.yaml`,
+//! an environment-specific overlay, and `APP_`-prefixed environment variables.
+
+use std::{net::IpAddr, sync::OnceLock, time::Duration};
+
+use secrecy::SecretString;
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+use sqlx::postgres::{PgConnectOptions, PgSslMode};
+
+use crate::web::data::SubscriberEmail;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error(transparent)]
+    Read(#[from] config::ConfigError),
+
+    #[error("unknown environment: '{0}'. Use either `local` or `production`")]
+    UnknownEnvironment(String),
+
+    #[error("'{0}' is not a valid sender email address")]
+    InvalidSenderEmail(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    pub db_config: DbConfig,
+    pub net_config: NetConfig,
+    pub email_config: EmailConfig,
+}
+
+impl AppConfig {
+    fn read() -> Result<Self, ConfigError> {
+        let base_path = std::env::current_dir()?.join("configuration");
+        let environment: Environment = std::env::var("APP_ENVIRONMENT")
+            .unwrap_or_else(|_| "local".into())
+            .try_into()?;
+
+        let settings = config::Config::builder()
+            .add_source(config::File::from(base_path.join("base.yaml")))
+            .add_source(
+                config::File::from(base_path.join(environment.as_str()).with_extension("yaml"))
+                    .required(false),
+            )
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("_")
+                    .separator("__"),
+            )
+            .build()?;
+
+        Ok(settings.try_deserialize::<AppConfig>()?)
+    }
+}
+
+/// Load the config once per process and hand out a `'static` reference to it from then on.
+pub fn get_or_init_config() -> &'static AppConfig {
+    static CONFIG: OnceLock<AppConfig> = OnceLock::new();
+    CONFIG.get_or_init(|| AppConfig::read().expect("Failed to read configuration."))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DbConfig {
+    pub username: String,
+    pub password: SecretString,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub port: u16,
+    pub host: String,
+    pub db_name: String,
+    pub require_ssl: bool,
+}
+
+impl DbConfig {
+    /// Connection options pointing at the Postgres instance, without selecting a database.
+    /// Used to create a fresh per-test database.
+    pub fn without_db(&self) -> PgConnectOptions {
+        use secrecy::ExposeSecret;
+
+        let ssl_mode = if self.require_ssl {
+            PgSslMode::Require
+        } else {
+            PgSslMode::Prefer
+        };
+
+        PgConnectOptions::new()
+            .host(&self.host)
+            .username(&self.username)
+            .password(self.password.expose_secret())
+            .port(self.port)
+            .ssl_mode(ssl_mode)
+    }
+
+    pub fn with_db(&self) -> PgConnectOptions {
+        self.without_db().database(&self.db_name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetConfig {
+    pub host: IpAddr,
+    pub app_port: u16,
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    pub url: String,
+    pub sender_email: String,
+    pub auth_token: SecretString,
+    pub timeout_millis: u64,
+    /// Retries attempted after a transient send failure. `0` disables retrying -
+    /// tests set this so delivery failures are immediate and deterministic.
+    pub retry_count: u32,
+    pub retry_base_delay_millis: u64,
+    pub retry_max_delay_millis: u64,
+}
+
+impl EmailConfig {
+    pub fn valid_sender(&self) -> Result<SubscriberEmail, ConfigError> {
+        SubscriberEmail::parse(self.sender_email.clone())
+            .map_err(|_| ConfigError::InvalidSenderEmail(self.sender_email.clone()))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_millis)
+    }
+
+    pub fn retry_config(&self) -> crate::email_client::RetryConfig {
+        crate::email_client::RetryConfig {
+            retry_count: self.retry_count,
+            base_delay: Duration::from_millis(self.retry_base_delay_millis),
+            max_delay: Duration::from_millis(self.retry_max_delay_millis),
+        }
+    }
+}
+
+enum Environment {
+    Local,
+    Production,
+}
+
+impl Environment {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Local => "local",
+            Environment::Production => "production",
+        }
+    }
+}
+
+impl TryFrom<String> for Environment {
+    type Error = ConfigError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "production" => Ok(Self::Production),
+            other => Err(ConfigError::UnknownEnvironment(other.to_string())),
+        }
+    }
+}
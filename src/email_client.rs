@@ -0,0 +1,287 @@
+//! A thin client over the transactional email API (e.g. Postmark) used to
+//! deliver confirmation links and newsletter issues.
+
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::Client;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+
+use crate::web::data::SubscriberEmail;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailClientError {
+    #[error("failed to build the http client: {0}")]
+    ClientBuild(#[source] reqwest::Error),
+
+    #[error("failed to send email: {0}")]
+    Send(#[source] reqwest::Error),
+}
+
+/// How `EmailClient::send_email` backs off between retries of a transient failure.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// Retries attempted after the initial try. `0` disables retrying entirely.
+    pub retry_count: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+#[derive(Clone)]
+pub struct EmailClient {
+    http_client: Client,
+    base_url: String,
+    sender: SubscriberEmail,
+    auth_token: SecretString,
+    retry_config: RetryConfig,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendEmailRequest<'a> {
+    from: &'a str,
+    to: &'a str,
+    subject: &'a str,
+    html_body: &'a str,
+    text_body: &'a str,
+}
+
+impl EmailClient {
+    pub fn new(
+        base_url: String,
+        sender: SubscriberEmail,
+        auth_token: SecretString,
+        timeout: Duration,
+        retry_config: RetryConfig,
+    ) -> Result<Self, EmailClientError> {
+        let http_client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(EmailClientError::ClientBuild)?;
+
+        Ok(Self {
+            http_client,
+            base_url,
+            sender,
+            auth_token,
+            retry_config,
+        })
+    }
+
+    /// Send one email, retrying a transient failure (timeout, connection error, or a
+    /// 5xx response) with exponential backoff and jitter. A 4xx response is treated as
+    /// permanent and returned immediately, without retrying.
+    pub async fn send_email(
+        &self,
+        recipient: &SubscriberEmail,
+        subject: &str,
+        html_body: &str,
+        text_body: &str,
+    ) -> Result<(), EmailClientError> {
+        let url = format!("{}/email", self.base_url);
+        let request_body = SendEmailRequest {
+            from: self.sender.as_ref(),
+            to: recipient.as_ref(),
+            subject,
+            html_body,
+            text_body,
+        };
+
+        let mut attempt = 0u32;
+        loop {
+            let outcome = self
+                .http_client
+                .post(&url)
+                .header("X-Postmark-Server-Token", self.auth_token.expose_secret())
+                .json(&request_body)
+                .send()
+                .await;
+
+            match outcome {
+                Ok(response) => match response.error_for_status() {
+                    Ok(_) => return Ok(()),
+                    Err(e) if e.status().is_some_and(|s| s.is_client_error()) => {
+                        return Err(EmailClientError::Send(e));
+                    }
+                    Err(e) if attempt >= self.retry_config.retry_count => {
+                        return Err(EmailClientError::Send(e));
+                    }
+                    Err(_) => {}
+                },
+                Err(e) if attempt >= self.retry_config.retry_count => {
+                    return Err(EmailClientError::Send(e));
+                }
+                Err(_) => {}
+            }
+
+            tokio::time::sleep(self.backoff_delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let RetryConfig {
+            base_delay,
+            max_delay,
+            ..
+        } = self.retry_config;
+
+        let exponential = base_delay
+            .checked_mul(2u32.saturating_pow(attempt.min(16)))
+            .unwrap_or(max_delay)
+            .min(max_delay);
+
+        let jitter_bound = (exponential.as_millis() as u64) / 4 + 1;
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=jitter_bound));
+
+        (exponential + jitter).min(max_delay + jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fake::{
+        faker::{
+            lorem::en::{Paragraph, Sentence},
+            name::en::Name,
+        },
+        Fake,
+    };
+    use secrecy::SecretString;
+    use wiremock::{
+        matchers::{any, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn email_client(base_url: String, retry_config: RetryConfig) -> EmailClient {
+        EmailClient::new(
+            base_url,
+            SubscriberEmail::parse(format!("{}@example.com", Name().fake::<String>())).unwrap(),
+            SecretString::from("auth-token".to_string()),
+            Duration::from_millis(200),
+            retry_config,
+        )
+        .unwrap()
+    }
+
+    fn no_retries() -> RetryConfig {
+        RetryConfig {
+            retry_count: 0,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        }
+    }
+
+    fn subject_body_and_content() -> (String, String, String) {
+        (Sentence(1..2).fake(), Paragraph(1..10).fake(), Paragraph(1..10).fake())
+    }
+
+    #[tokio::test]
+    async fn send_email_succeeds_if_server_returns_200() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri(), no_retries());
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let recipient =
+            SubscriberEmail::parse(format!("{}@example.com", Name().fake::<String>())).unwrap();
+        let (subject, html, text) = subject_body_and_content();
+
+        let outcome = email_client.send_email(&recipient, &subject, &html, &text).await;
+
+        assert!(outcome.is_ok());
+    }
+
+    #[tokio::test]
+    async fn send_email_fails_without_retrying_on_a_4xx_response() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(mock_server.uri(), no_retries());
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let recipient =
+            SubscriberEmail::parse(format!("{}@example.com", Name().fake::<String>())).unwrap();
+        let (subject, html, text) = subject_body_and_content();
+
+        let outcome = email_client.send_email(&recipient, &subject, &html, &text).await;
+
+        assert!(outcome.is_err());
+    }
+
+    #[tokio::test]
+    async fn send_email_retries_a_5xx_response_and_eventually_succeeds() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(
+            mock_server.uri(),
+            RetryConfig {
+                retry_count: 2,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(50),
+            },
+        );
+
+        // Two failures then a success - the third attempt should go through.
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let recipient =
+            SubscriberEmail::parse(format!("{}@example.com", Name().fake::<String>())).unwrap();
+        let (subject, html, text) = subject_body_and_content();
+
+        let outcome = email_client.send_email(&recipient, &subject, &html, &text).await;
+
+        assert!(outcome.is_ok());
+    }
+
+    #[tokio::test]
+    async fn send_email_gives_up_after_exhausting_its_retry_budget() {
+        let mock_server = MockServer::start().await;
+        let email_client = email_client(
+            mock_server.uri(),
+            RetryConfig {
+                retry_count: 2,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(50),
+            },
+        );
+
+        // 1 initial attempt + 2 retries = 3 calls total, all failing.
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let recipient =
+            SubscriberEmail::parse(format!("{}@example.com", Name().fake::<String>())).unwrap();
+        let (subject, html, text) = subject_body_and_content();
+
+        let outcome = email_client.send_email(&recipient, &subject, &html, &text).await;
+
+        assert!(outcome.is_err());
+    }
+}
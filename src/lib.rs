@@ -0,0 +1,13 @@
+pub mod app;
+pub mod config;
+pub mod email_client;
+pub mod model;
+pub mod templ_manager;
+pub mod utils;
+pub mod web;
+
+mod error;
+
+pub use app::{serve, App};
+pub use email_client::EmailClient;
+pub use error::{Error, Result};
@@ -0,0 +1,57 @@
+//! Owns the database connection pool and runs migrations. All modules that
+//! need to talk to Postgres do so through a `ModelManager`.
+
+use sqlx::postgres::{PgPoolOptions, Postgres};
+use sqlx::{Connection, Executor, Pool};
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModelError {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error(transparent)]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+}
+
+#[derive(Clone)]
+pub struct ModelManager {
+    db: Pool<Postgres>,
+}
+
+impl ModelManager {
+    /// Connect to the database configured in `config` and run pending migrations.
+    pub async fn init(config: &AppConfig) -> Result<Self, ModelError> {
+        let db = PgPoolOptions::new()
+            .connect_lazy_with(config.db_config.with_db());
+
+        sqlx::migrate!("./migrations").run(&db).await?;
+
+        Ok(ModelManager { db })
+    }
+
+    /// Create a fresh database named `config.db_config.db_name` and migrate it. Used by
+    /// the test suite, which gives each test a random name so each test gets full isolation.
+    pub async fn configure_for_test(config: &AppConfig) -> Result<(), ModelError> {
+        let mut connection =
+            sqlx::PgConnection::connect_with(&config.db_config.without_db()).await?;
+        connection
+            .execute(format!(r#"CREATE DATABASE "{}";"#, config.db_config.db_name).as_str())
+            .await?;
+
+        let db = PgPoolOptions::new().connect_with(config.db_config.with_db()).await?;
+        sqlx::migrate!("./migrations").run(&db).await?;
+
+        Ok(())
+    }
+
+    pub fn db(&self) -> &Pool<Postgres> {
+        &self.db
+    }
+
+    pub fn new_id() -> Uuid {
+        Uuid::new_v4()
+    }
+}
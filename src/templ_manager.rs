@@ -0,0 +1,24 @@
+//! Minimal holder for the handful of string templates the app renders
+//! (confirmation emails, newsletter issues, ...).
+
+#[derive(Clone)]
+pub struct TemplateManager;
+
+impl TemplateManager {
+    pub fn init() -> Self {
+        TemplateManager
+    }
+
+    pub fn confirmation_email_html(&self, confirmation_link: &str) -> String {
+        format!(
+            "Welcome to our newsletter!<br />\
+             Click <a href=\"{confirmation_link}\">here</a> to confirm your subscription."
+        )
+    }
+
+    pub fn confirmation_email_text(&self, confirmation_link: &str) -> String {
+        format!(
+            "Welcome to our newsletter!\nVisit {confirmation_link} to confirm your subscription."
+        )
+    }
+}
@@ -0,0 +1,19 @@
+//! Small, dependency-free helpers shared across modules.
+
+use base64::Engine;
+
+#[derive(Debug, thiserror::Error)]
+#[error("failed to decode base64 string: {0}")]
+pub struct B64DecodeError(#[from] base64::DecodeError);
+
+/// Base64-encode `data` using the standard alphabet (with padding).
+pub fn b64_encode(data: impl AsRef<[u8]>) -> String {
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
+/// Base64-decode `b64` into a UTF-8 `String`.
+pub fn b64_decode(b64: &str) -> Result<String, B64DecodeError> {
+    let bytes = base64::engine::general_purpose::STANDARD.decode(b64)?;
+    // Invalid utf-8 is not rejected: offending bytes are replaced with U+FFFD.
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
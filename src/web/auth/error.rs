@@ -1,3 +1,8 @@
+use axum::{
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+
 #[derive(Debug, thiserror::Error)]
 pub enum AuthError {
     #[error("the user doesn't have authorization: {0}")]
@@ -16,4 +21,29 @@ pub enum AuthError {
     Sqlx(#[from] sqlx::Error),
     #[error("base64 decoding error: {0}")]
     Base64Decode(#[from] crate::utils::B64DecodeError),
+    #[error("failed to verify password hash: {0}")]
+    Argon2(#[from] argon2::password_hash::Error),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            AuthError::InvalidLoginParams(_)
+            | AuthError::MissingAuthHeader
+            | AuthError::InvalidUtf(_)
+            | AuthError::MissingColon
+            | AuthError::WrongAuthSchema(_)
+            | AuthError::Base64Decode(_) => StatusCode::UNAUTHORIZED,
+            AuthError::Sqlx(_) | AuthError::Argon2(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let mut res = (status, self.to_string()).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            res.headers_mut().insert(
+                axum::http::header::WWW_AUTHENTICATE,
+                HeaderValue::from_static(r#"Basic realm="publish""#),
+            );
+        }
+        res
+    }
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+pub mod error;
+pub mod users;
+
+pub use error::AuthError;
+pub use users::validate_credentials;
+
+use axum::http::HeaderMap;
+
+use crate::utils::b64_decode;
+
+/// Username/password pair extracted from an `Authorization: Basic` header.
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Pull a `Basic` auth `Credentials` out of the request headers.
+///
+/// This only parses and base64-decodes the header - it does not check the
+/// credentials against anything, that's up to the caller.
+pub fn basic_credentials(headers: &HeaderMap) -> Result<Credentials, AuthError> {
+    let header_value = headers
+        .get("Authorization")
+        .ok_or(AuthError::MissingAuthHeader)?
+        .to_str()
+        .map_err(|e| AuthError::InvalidUtf(e.to_string()))?;
+
+    let b64_segment = header_value
+        .strip_prefix("Basic ")
+        .ok_or_else(|| AuthError::WrongAuthSchema("Basic".to_string()))?;
+
+    let decoded = b64_decode(b64_segment)?;
+
+    let mut parts = decoded.splitn(2, ':');
+    let username = parts.next().ok_or(AuthError::MissingColon)?.to_string();
+    let password = parts.next().ok_or(AuthError::MissingColon)?.to_string();
+
+    Ok(Credentials { username, password })
+}
@@ -0,0 +1,67 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use secrecy::{ExposeSecret, SecretString};
+use tokio::task;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::model::ModelManager;
+
+use super::{error::AuthError, Credentials};
+
+/// A PHC-format hash of a password nobody actually uses. Verifying against it when the
+/// username is unknown means an unknown-user request takes roughly as long as a
+/// known-user one, so the response timing doesn't leak which usernames exist.
+const FALLBACK_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$\
+    gZiV/M1gPc22ElAH/Jh1Hw$CWOrkoo7oJBQ/iyh7uJ0LO2aLEfUMJK/R9cQoiBnLE8";
+
+/// Check `credentials` against the `users` table, returning the matching `user_id`.
+#[instrument(name = "Validate credentials", skip(mm, credentials))]
+pub async fn validate_credentials(
+    mm: &ModelManager,
+    credentials: Credentials,
+) -> Result<Uuid, AuthError> {
+    let mut user_id = None;
+    let mut expected_password_hash = SecretString::from(FALLBACK_PASSWORD_HASH.to_string());
+
+    if let Some((stored_user_id, stored_password_hash)) =
+        stored_credentials(mm, &credentials.username).await?
+    {
+        user_id = Some(stored_user_id);
+        expected_password_hash = stored_password_hash;
+    }
+
+    let password = credentials.password;
+    // Argon2 verification is CPU-bound - run it on a blocking thread so it doesn't
+    // stall other requests sharing this worker's async runtime.
+    task::spawn_blocking(move || verify_password_hash(expected_password_hash, password))
+        .await
+        .expect("the password-verification task panicked")?;
+
+    user_id.ok_or_else(|| AuthError::InvalidLoginParams(credentials.username.clone()))
+}
+
+fn verify_password_hash(
+    expected_password_hash: SecretString,
+    password_candidate: String,
+) -> Result<(), AuthError> {
+    let expected_password_hash = PasswordHash::new(expected_password_hash.expose_secret())?;
+
+    Argon2::default()
+        .verify_password(password_candidate.as_bytes(), &expected_password_hash)
+        .map_err(|_| AuthError::InvalidLoginParams("wrong password".to_string()))
+}
+
+#[instrument(name = "Get stored credentials", skip(mm, username))]
+async fn stored_credentials(
+    mm: &ModelManager,
+    username: &str,
+) -> Result<Option<(Uuid, SecretString)>, AuthError> {
+    let row = sqlx::query!(
+        r#"SELECT user_id, password_hash FROM users WHERE username = $1"#,
+        username
+    )
+    .fetch_optional(mm.db())
+    .await?;
+
+    Ok(row.map(|r| (r.user_id, SecretString::from(r.password_hash))))
+}
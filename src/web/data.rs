@@ -0,0 +1,148 @@
+//! Request/response payloads and their validated counterparts.
+
+use serde::Deserialize;
+use unicode_segmentation::UnicodeSegmentation;
+use validator::ValidateEmail;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct ValidationError(pub String);
+
+/// Raw, untrusted subscriber payload as received on `POST /api/subscribe`.
+#[derive(Debug, Deserialize)]
+pub struct DeserSubscriber {
+    pub name: String,
+    pub email: String,
+}
+
+impl DeserSubscriber {
+    pub fn new(name: String, email: String) -> Self {
+        Self { name, email }
+    }
+}
+
+/// A subscriber whose `name` and `email` have both passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidSubscriber {
+    pub name: SubscriberName,
+    pub email: SubscriberEmail,
+}
+
+impl TryFrom<DeserSubscriber> for ValidSubscriber {
+    type Error = ValidationError;
+
+    fn try_from(deser: DeserSubscriber) -> Result<Self, Self::Error> {
+        let name = SubscriberName::parse(deser.name)?;
+        let email = SubscriberEmail::parse(deser.email)?;
+        Ok(Self { name, email })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberName(String);
+
+impl SubscriberName {
+    pub fn parse(s: String) -> Result<Self, ValidationError> {
+        let is_empty_or_whitespace = s.trim().is_empty();
+        let is_too_long = s.graphemes(true).count() > 256;
+        let forbidden_chars = ['/', '(', ')', '"', '<', '>', '\\', '{', '}'];
+        let contains_forbidden_chars = s.chars().any(|g| forbidden_chars.contains(&g));
+
+        if is_empty_or_whitespace || is_too_long || contains_forbidden_chars {
+            Err(ValidationError(format!("'{s}' is not a valid subscriber name.")))
+        } else {
+            Ok(Self(s))
+        }
+    }
+}
+
+impl AsRef<str> for SubscriberName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberEmail(String);
+
+impl SubscriberEmail {
+    pub fn parse(s: String) -> Result<Self, ValidationError> {
+        if s.validate_email() {
+            Ok(Self(s))
+        } else {
+            Err(ValidationError(format!("'{s}' is not a valid subscriber email.")))
+        }
+    }
+}
+
+impl AsRef<str> for SubscriberEmail {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SubscriberEmail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+
+    #[test]
+    fn a_256_grapheme_long_name_is_valid() {
+        let name = "ё".repeat(256);
+        assert_ok!(SubscriberName::parse(name));
+    }
+
+    #[test]
+    fn a_name_longer_than_256_graphemes_is_rejected() {
+        let name = "a".repeat(257);
+        assert_err!(SubscriberName::parse(name));
+    }
+
+    #[test]
+    fn whitespace_only_names_are_rejected() {
+        assert_err!(SubscriberName::parse("   ".to_string()));
+    }
+
+    #[test]
+    fn empty_string_is_rejected() {
+        assert_err!(SubscriberName::parse("".to_string()));
+    }
+
+    #[test]
+    fn names_containing_forbidden_characters_are_rejected() {
+        for name in ['/', '(', ')', '"', '<', '>', '\\', '{', '}'] {
+            assert_err!(SubscriberName::parse(name.to_string()));
+        }
+    }
+
+    #[test]
+    fn a_valid_name_is_accepted() {
+        assert_ok!(SubscriberName::parse("Ursula Le Guin".to_string()));
+    }
+
+    #[test]
+    fn empty_string_is_rejected_for_email() {
+        assert_err!(SubscriberEmail::parse("".to_string()));
+    }
+
+    #[test]
+    fn email_missing_at_symbol_is_rejected() {
+        assert_err!(SubscriberEmail::parse("ursuladomain.com".to_string()));
+    }
+
+    #[test]
+    fn email_missing_subject_is_rejected() {
+        assert_err!(SubscriberEmail::parse("@domain.com".to_string()));
+    }
+
+    #[test]
+    fn a_valid_email_is_accepted() {
+        assert_ok!(SubscriberEmail::parse("ursula_le_guin@gmail.com".to_string()));
+    }
+}
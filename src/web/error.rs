@@ -0,0 +1,70 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::web::{auth::error::AuthError, data::ValidationError, idempotency::IdempotencyKeyError};
+
+pub type Result<T> = core::result::Result<T, WebError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebError {
+    #[error("invalid subscriber data: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error(transparent)]
+    EmailClient(#[from] crate::email_client::EmailClientError),
+
+    #[error("no subscriber matches the given confirmation token")]
+    UnknownConfirmationToken,
+
+    #[error("missing or malformed 'Idempotency-Key' header")]
+    MissingIdempotencyKey,
+
+    #[error("invalid idempotency key: {0}")]
+    InvalidIdempotencyKey(#[from] IdempotencyKeyError),
+
+    #[error("a request with this idempotency key is still being processed")]
+    IdempotencyInProgress,
+
+    #[error("failed to buffer response body: {0}")]
+    BodyBuffering(String),
+}
+
+impl IntoResponse for WebError {
+    fn into_response(self) -> Response {
+        match self {
+            WebError::Validation(e) => (StatusCode::BAD_REQUEST, e.0).into_response(),
+            WebError::Auth(e) => e.into_response(),
+            WebError::Sqlx(e) => {
+                error!("{e}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            WebError::EmailClient(e) => {
+                error!("{e}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            WebError::UnknownConfirmationToken => {
+                (StatusCode::NOT_FOUND, self.to_string()).into_response()
+            }
+            WebError::MissingIdempotencyKey | WebError::InvalidIdempotencyKey(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+            WebError::IdempotencyInProgress => {
+                (StatusCode::CONFLICT, self.to_string()).into_response()
+            }
+            WebError::BodyBuffering(e) => {
+                error!("{e}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
@@ -0,0 +1,33 @@
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct IdempotencyKeyError(String);
+
+/// A client-supplied `Idempotency-Key` header value, validated to be non-empty
+/// and reasonably short before it's used as a primary-key column.
+#[derive(Debug, Clone)]
+pub struct IdempotencyKey(String);
+
+impl TryFrom<String> for IdempotencyKey {
+    type Error = IdempotencyKeyError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        if s.is_empty() {
+            return Err(IdempotencyKeyError(
+                "the idempotency key cannot be empty".to_string(),
+            ));
+        }
+        const MAX_LENGTH: usize = 50;
+        if s.len() >= MAX_LENGTH {
+            return Err(IdempotencyKeyError(format!(
+                "the idempotency key must be shorter than {MAX_LENGTH} characters"
+            )));
+        }
+        Ok(Self(s))
+    }
+}
+
+impl AsRef<str> for IdempotencyKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
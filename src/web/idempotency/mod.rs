@@ -0,0 +1,9 @@
+//! Lets a client safely retry `POST /api/news` after a timeout without the
+//! newsletter issue going out twice: the first request's response is saved
+//! and later replayed verbatim for the same `(user_id, idempotency_key)` pair.
+
+mod key;
+mod persistence;
+
+pub use key::{IdempotencyKey, IdempotencyKeyError};
+pub use persistence::{save_response, try_processing, NextAction};
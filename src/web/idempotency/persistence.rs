@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{HeaderName, HeaderValue, StatusCode},
+    response::Response,
+};
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+use uuid::Uuid;
+
+use super::IdempotencyKey;
+use crate::{model::ModelManager, web::error::WebError};
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "header_pair")]
+struct HeaderPairRecord {
+    name: String,
+    value: Vec<u8>,
+}
+
+impl PgHasArrayType for HeaderPairRecord {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_header_pair")
+    }
+}
+
+pub enum NextAction {
+    StartProcessing,
+    ReturnSavedResponse(Response),
+}
+
+/// How long a loser request is willing to poll for the winner's saved response
+/// before giving up. Bounds the wait if the winner dies (e.g. errors out) after
+/// claiming the key but before calling [`save_response`], which would otherwise
+/// leave `response_status_code` `NULL` forever and hang every later request.
+const MAX_POLL_WAIT: Duration = Duration::from_secs(10);
+
+/// Claim `idempotency_key` for `user_id`, or find out someone else already did.
+///
+/// The very first caller for a given key inserts the placeholder row and gets
+/// `StartProcessing`. Anyone else racing them on the unique `(user_id,
+/// idempotency_key)` primary key hits the `ON CONFLICT DO NOTHING` and instead
+/// polls until the winner's response has been saved.
+pub async fn try_processing(
+    mm: &ModelManager,
+    idempotency_key: &IdempotencyKey,
+    user_id: Uuid,
+) -> Result<NextAction, WebError> {
+    let n_inserted_rows = sqlx::query!(
+        r#"
+        INSERT INTO idempotency (user_id, idempotency_key, created_at)
+        VALUES ($1, $2, now())
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        idempotency_key.as_ref()
+    )
+    .execute(mm.db())
+    .await?
+    .rows_affected();
+
+    if n_inserted_rows > 0 {
+        return Ok(NextAction::StartProcessing);
+    }
+
+    let deadline = tokio::time::Instant::now() + MAX_POLL_WAIT;
+    loop {
+        if let Some(saved_response) = get_saved_response(mm, idempotency_key, user_id).await? {
+            return Ok(NextAction::ReturnSavedResponse(saved_response));
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(WebError::IdempotencyInProgress);
+        }
+        // The request that's already processing hasn't saved its response yet.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+async fn get_saved_response(
+    mm: &ModelManager,
+    idempotency_key: &IdempotencyKey,
+    user_id: Uuid,
+) -> Result<Option<Response>, WebError> {
+    let saved = sqlx::query!(
+        r#"
+        SELECT
+            response_status_code as "response_status_code!",
+            response_headers as "response_headers!: Vec<HeaderPairRecord>",
+            response_body as "response_body!"
+        FROM idempotency
+        WHERE user_id = $1 AND idempotency_key = $2 AND response_status_code IS NOT NULL
+        "#,
+        user_id,
+        idempotency_key.as_ref()
+    )
+    .fetch_optional(mm.db())
+    .await?;
+
+    let Some(row) = saved else {
+        return Ok(None);
+    };
+
+    let status_code = StatusCode::from_u16(row.response_status_code.try_into().unwrap_or(500))
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut response = Response::builder().status(status_code);
+    for HeaderPairRecord { name, value } in row.response_headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_bytes(&value),
+        ) {
+            response = response.header(name, value);
+        }
+    }
+
+    let response = response
+        .body(Body::from(row.response_body))
+        .expect("a response built from previously-saved, already-valid parts");
+    Ok(Some(response))
+}
+
+/// Persist `response` as the saved reply for `(user_id, idempotency_key)` and hand
+/// back an equivalent `Response` for the current request to actually return.
+pub async fn save_response(
+    mm: &ModelManager,
+    idempotency_key: &IdempotencyKey,
+    user_id: Uuid,
+    response: Response,
+) -> Result<Response, WebError> {
+    let (parts, body) = response.into_parts();
+    let body_bytes = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| WebError::BodyBuffering(e.to_string()))?;
+
+    let status_code = parts.status.as_u16() as i16;
+    let headers = parts
+        .headers
+        .iter()
+        .map(|(name, value)| HeaderPairRecord {
+            name: name.as_str().to_owned(),
+            value: value.as_bytes().to_owned(),
+        })
+        .collect::<Vec<_>>();
+
+    sqlx::query_unchecked!(
+        r#"
+        UPDATE idempotency
+        SET response_status_code = $3, response_headers = $4, response_body = $5
+        WHERE user_id = $1 AND idempotency_key = $2
+        "#,
+        user_id,
+        idempotency_key.as_ref(),
+        status_code,
+        headers,
+        body_bytes.as_ref()
+    )
+    .execute(mm.db())
+    .await?;
+
+    let response = Response::from_parts(parts, Body::from(body_bytes));
+    Ok(response)
+}
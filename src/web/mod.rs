@@ -0,0 +1,28 @@
+pub mod auth;
+pub mod data;
+pub mod error;
+pub mod idempotency;
+mod news;
+mod subscriptions;
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::app::AppState;
+
+pub use error::WebError;
+
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/health-check", get(health_check))
+        .route("/api/subscribe", post(subscriptions::subscribe))
+        .route("/subscribe/confirm", get(subscriptions::confirm))
+        .route("/api/news", post(news::publish_newsletter))
+        .with_state(app_state)
+}
+
+async fn health_check() -> axum::http::StatusCode {
+    axum::http::StatusCode::OK
+}
@@ -0,0 +1,126 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use tracing::{instrument, warn};
+
+use crate::{
+    app::AppState,
+    model::ModelManager,
+    web::{
+        auth::{basic_credentials, validate_credentials},
+        data::{DeserSubscriber, ValidSubscriber, ValidationError},
+        error::{Result, WebError},
+        idempotency::{self, IdempotencyKey, NextAction},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct BodyData {
+    title: String,
+    content: Content,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Content {
+    text: String,
+    html: String,
+}
+
+#[instrument(name = "Publishing a newsletter issue", skip(state, headers, body))]
+pub async fn publish_newsletter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<BodyData>,
+) -> Result<Response> {
+    let credentials = basic_credentials(&headers)?;
+    let user_id = validate_credentials(&state.model_mgr, credentials).await?;
+
+    let idempotency_key = headers
+        .get("Idempotency-Key")
+        .ok_or(WebError::MissingIdempotencyKey)?
+        .to_str()
+        .map_err(|_| WebError::MissingIdempotencyKey)?
+        .to_string();
+    let idempotency_key = IdempotencyKey::try_from(idempotency_key)?;
+
+    let response = match idempotency::try_processing(&state.model_mgr, &idempotency_key, user_id)
+        .await?
+    {
+        NextAction::ReturnSavedResponse(saved_response) => saved_response,
+        NextAction::StartProcessing => {
+            let n_failed = deliver_to_confirmed_subscribers(&state, &body).await?;
+
+            let mut response = StatusCode::OK.into_response();
+            response.headers_mut().insert(
+                "X-Failed-Deliveries",
+                axum::http::HeaderValue::from(n_failed as u64),
+            );
+            idempotency::save_response(&state.model_mgr, &idempotency_key, user_id, response)
+                .await?
+        }
+    };
+
+    Ok(response)
+}
+
+async fn deliver_to_confirmed_subscribers(state: &AppState, body: &BodyData) -> Result<usize> {
+    let subscribers = confirmed_subscribers(&state.model_mgr).await?;
+
+    let mut n_failed = 0usize;
+    for subscriber in subscribers {
+        match subscriber {
+            Ok(subscriber) => {
+                if let Err(e) = state
+                    .email_client
+                    .send_email(
+                        &subscriber.email,
+                        &body.title,
+                        &body.content.html,
+                        &body.content.text,
+                    )
+                    .await
+                {
+                    warn!(
+                        error.cause_chain = ?e,
+                        "Skipping a confirmed subscriber - failed to deliver the newsletter issue to them."
+                    );
+                    n_failed += 1;
+                }
+            }
+            Err(e) => {
+                warn!(
+                    error.cause_chain = ?e,
+                    "Skipping a stored subscriber - their record is not valid."
+                );
+            }
+        }
+    }
+
+    if n_failed > 0 {
+        warn!(n_failed, "Failed to deliver the newsletter issue to some subscribers");
+    }
+
+    Ok(n_failed)
+}
+
+/// Every `confirmed` subscriber, re-validated on the way out so a single corrupt row
+/// can't take down the whole send.
+#[instrument(name = "Fetching confirmed subscribers", skip(mm))]
+async fn confirmed_subscribers(
+    mm: &ModelManager,
+) -> Result<Vec<std::result::Result<ValidSubscriber, ValidationError>>> {
+    let rows = sqlx::query!("SELECT email, name FROM subscriptions WHERE status = 'confirmed'")
+        .fetch_all(mm.db())
+        .await?;
+
+    let subscribers = rows
+        .into_iter()
+        .map(|r| ValidSubscriber::try_from(DeserSubscriber::new(r.name, r.email)))
+        .collect();
+
+    Ok(subscribers)
+}
@@ -0,0 +1,150 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    app::AppState,
+    model::ModelManager,
+    web::{
+        data::{DeserSubscriber, ValidSubscriber},
+        error::{Result, WebError},
+    },
+};
+
+#[instrument(name = "Adding a new subscriber", skip(state, deser_subscriber))]
+pub async fn subscribe(
+    State(state): State<AppState>,
+    Json(deser_subscriber): Json<DeserSubscriber>,
+) -> Result<StatusCode> {
+    let subscriber = ValidSubscriber::try_from(deser_subscriber)?;
+
+    // Both inserts must land together - a subscriber row with no matching token
+    // can never be confirmed.
+    let mut tx = state.model_mgr.db().begin().await?;
+    let subscriber_id = insert_subscriber(&mut tx, &subscriber).await?;
+    let subscription_token = generate_subscription_token();
+    store_token(&mut tx, subscriber_id, &subscription_token).await?;
+    tx.commit().await?;
+
+    let confirmation_link = format!(
+        "{}/subscribe/confirm?subscription_token={subscription_token}",
+        state.base_url
+    );
+
+    state
+        .email_client
+        .send_email(
+            &subscriber.email,
+            "Welcome!",
+            &state.templ_mgr.confirmation_email_html(&confirmation_link),
+            &state.templ_mgr.confirmation_email_text(&confirmation_link),
+        )
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmParams {
+    subscription_token: String,
+}
+
+// A missing `subscription_token` query parameter is rejected by the `Query`
+// extractor itself, before this handler ever runs, yielding the 400 we want.
+#[instrument(name = "Confirming a pending subscriber", skip(state))]
+pub async fn confirm(
+    State(state): State<AppState>,
+    Query(params): Query<ConfirmParams>,
+) -> Result<StatusCode> {
+    let subscriber_id = subscriber_id_for_token(&state.model_mgr, &params.subscription_token)
+        .await?
+        .ok_or(WebError::UnknownConfirmationToken)?;
+
+    confirm_subscriber(&state.model_mgr, subscriber_id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+#[instrument(name = "Saving new subscriber in the database", skip(tx, subscriber))]
+async fn insert_subscriber(
+    tx: &mut Transaction<'_, Postgres>,
+    subscriber: &ValidSubscriber,
+) -> Result<Uuid> {
+    let subscriber_id = ModelManager::new_id();
+    // `email` is UNIQUE - a resubscribe is not an error, it should just hand back
+    // the existing row's id (the `DO UPDATE` is a no-op, but lets us `RETURNING id`
+    // on the conflict path too; a plain `DO NOTHING` would return no rows).
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO subscriptions (id, email, name, subscribed_at, status)
+        VALUES ($1, $2, $3, $4, 'pending_confirmation')
+        ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email
+        RETURNING id
+        "#,
+        subscriber_id,
+        subscriber.email.as_ref(),
+        subscriber.name.as_ref(),
+        OffsetDateTime::now_utc()
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(row.id)
+}
+
+#[instrument(name = "Storing a new subscription token in the database", skip(tx, token))]
+async fn store_token(
+    tx: &mut Transaction<'_, Postgres>,
+    subscriber_id: Uuid,
+    token: &str,
+) -> Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO subscription_tokens (subscription_token, subscriber_id) VALUES ($1, $2)"#,
+        token,
+        subscriber_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(name = "Looking up a subscriber id from a subscription token", skip(mm, token))]
+async fn subscriber_id_for_token(mm: &ModelManager, token: &str) -> Result<Option<Uuid>> {
+    let row = sqlx::query!(
+        r#"SELECT subscriber_id FROM subscription_tokens WHERE subscription_token = $1"#,
+        token
+    )
+    .fetch_optional(mm.db())
+    .await?;
+
+    Ok(row.map(|r| r.subscriber_id))
+}
+
+#[instrument(name = "Marking a subscriber as confirmed", skip(mm))]
+async fn confirm_subscriber(mm: &ModelManager, subscriber_id: Uuid) -> Result<()> {
+    sqlx::query!(
+        r#"UPDATE subscriptions SET status = 'confirmed' WHERE id = $1"#,
+        subscriber_id
+    )
+    .execute(mm.db())
+    .await?;
+
+    Ok(())
+}
+
+fn generate_subscription_token() -> String {
+    thread_rng()
+        .sample_iter(Alphanumeric)
+        .map(char::from)
+        .take(25)
+        .collect()
+}
@@ -36,11 +36,53 @@ pub struct ConfirmationLinks {
     pub plain_text: reqwest::Url,
 }
 
+/// A randomly-generated user stored in the `users` table of a spawned `TestApp`,
+/// so tests can authenticate without relying on a hardcoded account.
+pub struct TestUser {
+    pub username: String,
+    pub password: String,
+}
+
+impl TestUser {
+    fn generate() -> Self {
+        Self {
+            username: Uuid::new_v4().to_string(),
+            password: Uuid::new_v4().to_string(),
+        }
+    }
+
+    async fn store(&self, mm: &ModelManager) -> Result<()> {
+        use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+
+        let salt = SaltString::generate(&mut rand::thread_rng());
+        let password_hash = Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            argon2::Params::new(19456, 2, 1, None).unwrap(),
+        )
+        .hash_password(self.password.as_bytes(), &salt)
+        .unwrap()
+        .to_string();
+
+        sqlx::query!(
+            "INSERT INTO users (user_id, username, password_hash) VALUES ($1, $2, $3)",
+            Uuid::new_v4(),
+            self.username,
+            password_hash,
+        )
+        .execute(mm.db())
+        .await?;
+
+        Ok(())
+    }
+}
+
 pub struct TestApp {
     pub http_client: Client,
     pub addr: SocketAddr,
     pub mm: ModelManager,
     pub email_server: MockServer,
+    pub test_user: TestUser,
 }
 impl TestApp {
     /// A helper function that tries to spawn a separate thread to serve our app
@@ -59,6 +101,8 @@ impl TestApp {
             // which will then be bound to the application.
             c.net_config.app_port = 0;
             c.email_config.url = email_server.uri();
+            // No retries by default, so delivery failures in a test are immediate.
+            c.email_config.retry_count = 0;
             c
         };
 
@@ -71,6 +115,9 @@ impl TestApp {
         let mm = app.app_state.model_mgr.clone();
         let http_client = Client::new();
 
+        let test_user = TestUser::generate();
+        test_user.store(&mm).await?;
+
         tokio::spawn(mailomat::serve(app));
 
         Ok(TestApp {
@@ -78,6 +125,7 @@ impl TestApp {
             addr,
             mm,
             email_server,
+            test_user,
         })
     }
 
@@ -113,6 +161,16 @@ impl TestApp {
     }
 
     pub async fn post_api_news(&self) -> Result<reqwest::Response> {
+        self.post_api_news_with_idempotency_key(&Uuid::new_v4().to_string())
+            .await
+    }
+
+    /// Same as [`Self::post_api_news`], but lets the caller pick (and reuse) the
+    /// `Idempotency-Key` header, so replay behavior can be asserted.
+    pub async fn post_api_news_with_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<reqwest::Response> {
         // A sketch of the current newsletter payload structure.
         let newsletter_req_body = json!({
             "title": "Newsletter title",
@@ -122,13 +180,14 @@ impl TestApp {
             }
         });
 
-        let creds = "admin:password";
+        let creds = format!("{}:{}", self.test_user.username, self.test_user.password);
         let b64_enc = b64_encode(creds);
 
         let res = self
             .http_client
             .post(&format!("http://{}/api/news", &self.addr))
             .header(reqwest::header::AUTHORIZATION, format!("Basic {b64_enc}"))
+            .header("Idempotency-Key", idempotency_key)
             .json(&newsletter_req_body)
             .send()
             .await?;
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use serial_test::serial;
+use uuid::Uuid;
+use wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+};
+
+use crate::helpers::TestApp;
+
+#[serial]
+#[tokio::test]
+async fn newsletter_is_delivered_to_confirmed_subscribers() -> Result<()> {
+    let app = TestApp::spawn().await?;
+    app.create_confirmed_subscriber().await?;
+
+    Mock::given(path("/email"))
+        .and(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .named("Deliver newsletter issue")
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let res = app.post_api_news().await?;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn newsletter_is_not_delivered_to_unconfirmed_subscribers() -> Result<()> {
+    let app = TestApp::spawn().await?;
+    // The confirmation email sent on subscribe is the only one we expect.
+    app.create_unconfirmed_subscriber().await?;
+
+    Mock::given(path("/email"))
+        .and(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .named("Deliver newsletter issue")
+        .expect(0)
+        .mount(&app.email_server)
+        .await;
+
+    let res = app.post_api_news().await?;
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn requests_without_credentials_are_rejected() -> Result<()> {
+    let app = TestApp::spawn().await?;
+
+    let res = app.post_unauthorized_api_news().await?;
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        res.headers()["WWW-Authenticate"],
+        r#"Basic realm="publish""#
+    );
+
+    Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn repeated_idempotency_key_replays_the_saved_response_without_resending() -> Result<()> {
+    let app = TestApp::spawn().await?;
+    app.create_confirmed_subscriber().await?;
+
+    Mock::given(path("/email"))
+        .and(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .named("Deliver newsletter issue")
+        .expect(1)
+        .mount(&app.email_server)
+        .await;
+
+    let idempotency_key = Uuid::new_v4().to_string();
+
+    let first = app
+        .post_api_news_with_idempotency_key(&idempotency_key)
+        .await?;
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let second = app
+        .post_api_news_with_idempotency_key(&idempotency_key)
+        .await?;
+    assert_eq!(second.status(), StatusCode::OK);
+
+    // The email mock expects exactly one delivery - a second send on replay
+    // would fail that expectation when the mock server is dropped.
+
+    Ok(())
+}
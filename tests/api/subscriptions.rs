@@ -222,3 +222,58 @@ async fn api_subscribe_sends_a_confirmation_email_with_a_link() -> Result<()> {
 
     Ok(())
 }
+
+#[serial]
+#[tokio::test]
+async fn confirm_without_token_returns_400() -> Result<()> {
+    let app = TestApp::spawn().await?;
+
+    let res = app
+        .http_client
+        .get(format!("http://{}/subscribe/confirm", app.addr))
+        .send()
+        .await?;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn confirm_with_unknown_token_returns_404() -> Result<()> {
+    let app = TestApp::spawn().await?;
+
+    let res = app
+        .http_client
+        .get(format!(
+            "http://{}/subscribe/confirm?subscription_token=unknown-token",
+            app.addr
+        ))
+        .send()
+        .await?;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[serial]
+#[tokio::test]
+async fn confirm_with_valid_token_confirms_the_subscriber() -> Result<()> {
+    let app = TestApp::spawn().await?;
+
+    let (links, subscriber) = app.create_unconfirmed_subscriber().await?;
+
+    let res = app.http_client.get(links.html).send().await?;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let (status,): (String,) =
+        sqlx::query_as("SELECT status FROM subscriptions WHERE email = $1")
+            .bind(subscriber.email.as_ref())
+            .fetch_one(app.mm.db())
+            .await?;
+    assert_eq!(status, "confirmed");
+
+    Ok(())
+}